@@ -3,10 +3,10 @@
 use crate::result::FloydWarshallResult;
 use safe_graph::{Graph, NodeTrait};
 use num_traits::Num;
-use std::clone::Clone;
 use std::cmp::Ord;
 use std::cmp::Ordering::{Greater, Less};
 use std::cmp::PartialOrd;
+use std::collections::HashMap;
 
 /// A trait group for `FloydWarshall`'s weighted edges.
 pub trait FloydWarshallTrait: Copy + Num + PartialOrd {}
@@ -14,6 +14,14 @@ pub trait FloydWarshallTrait: Copy + Num + PartialOrd {}
 /// Implement the `FloydWarshallTrait` for all types satisfying bounds.
 impl<F> FloydWarshallTrait for F where F: Copy + Num + PartialOrd {}
 
+/// Error returned by [`FloydWarshall::try_find_paths`] when a negative cycle is detected.
+///
+/// Detection is only meaningful for min-plus-like semirings (the default additive `op` together
+/// with the default `<` `cmp`): a node is considered to lie on a negative cycle once its
+/// self-distance becomes "better than identity", i.e. `cmp(self_weight, F::zero())` holds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NegativeCycle<N>(pub Vec<N>);
+
 /// Floyd-Warshall algorithm structure.
 ///
 /// # `FloydWarshall` algorithm is parameterized over:
@@ -44,6 +52,9 @@ pub struct FloydWarshall<F: FloydWarshallTrait> {
     cmp: Box<Fn(F, F) -> bool>,
     /// Discard loops (e.g. edges starting and ending in the same node) from calculation.
     discard_loops: bool,
+    /// Treat the input graph as undirected, i.e. seed every edge `(a, b, w)` symmetrically as
+    /// both `(a, b, w)` and `(b, a, w)` before running the algorithm.
+    undirected: bool,
 }
 
 impl<F: FloydWarshallTrait> FloydWarshall<F> {
@@ -65,6 +76,7 @@ impl<F: FloydWarshallTrait> FloydWarshall<F> {
             op: add,
             cmp: sharp_less,
             discard_loops: true,
+            undirected: false,
         }
     }
 
@@ -86,7 +98,7 @@ impl<F: FloydWarshallTrait> FloydWarshall<F> {
     /// let alg: FloydWarshall<f32> = FloydWarshall::new_customized(mul, sharp_greater);
     /// ```
     pub fn new_customized(op: Box<Fn(F, F) -> F>, cmp: Box<Fn(F, F) -> bool>) -> Self {
-        Self::new_fully_customized(op, cmp, true)
+        Self::new_fully_customized(op, cmp, true, false)
     }
 
     /// Create a new instance of FloydWarshall structure with customized settings.
@@ -96,6 +108,8 @@ impl<F: FloydWarshallTrait> FloydWarshall<F> {
     /// - the `cmp` (comparison) to be used for weighted paths
     /// - the `discard_loops` to discard loops (e.g. edges starting and ending in the same node)
     ///   from calculation.
+    /// - the `undirected` to treat the input graph as undirected, seeding every edge
+    ///   symmetrically before running the algorithm.
     ///
     /// # Examples
     ///
@@ -106,20 +120,101 @@ impl<F: FloydWarshallTrait> FloydWarshall<F> {
     /// let mul = Box::new(|x: f32, y: f32| x * y);
     /// let sharp_greater = Box::new(|x: f32, y: f32| x.partial_cmp(&y).unwrap_or(Less) == Greater);
     /// let discard_loops = false;
+    /// let undirected = false;
     ///
-    /// let alg: FloydWarshall<f32> = FloydWarshall::new_customized(mul, sharp_greater);
+    /// let alg: FloydWarshall<f32> =
+    ///     FloydWarshall::new_fully_customized(mul, sharp_greater, discard_loops, undirected);
+    /// ```
     pub fn new_fully_customized(
         op: Box<Fn(F, F) -> F>,
         cmp: Box<Fn(F, F) -> bool>,
         discard_loops: bool,
+        undirected: bool,
     ) -> Self {
         Self {
             op,
             cmp,
             discard_loops,
+            undirected,
         }
     }
 
+    /// Set whether the input graph should be treated as undirected, i.e. every edge
+    /// `(a, b, w)` is seeded symmetrically as both `(a, b, w)` and `(b, a, w)` before running
+    /// the algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use floyd_warshall_alg::FloydWarshall;
+    ///
+    /// let mut alg: FloydWarshall<f32> = FloydWarshall::new();
+    /// alg.set_undirected(true);
+    /// ```
+    pub fn set_undirected(&mut self, undirected: bool) {
+        self.undirected = undirected;
+    }
+
+    /// Create a new instance of FloydWarshall structure pre-wired for boolean reachability
+    /// (transitive closure): the `op` is logical AND expressed as multiplication (`0`/`1`
+    /// weights), and the `cmp` keeps a pair that just became reachable (`0` replaced by `1`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use floyd_warshall_alg::FloydWarshall;
+    ///
+    /// let alg: FloydWarshall<u8> = FloydWarshall::transitive_closure();
+    /// ```
+    pub fn transitive_closure() -> Self {
+        let and = Box::new(|x: F, y: F| x * y);
+        let newly_reachable = Box::new(|x: F, y: F| x.partial_cmp(&y).unwrap_or(Less) == Greater);
+
+        Self::new_customized(and, newly_reachable)
+    }
+
+    /// Create a new instance of FloydWarshall structure pre-wired for the widest path
+    /// (bottleneck shortest path) problem: the `op` keeps the minimum edge weight (capacity)
+    /// seen along the path, and the `cmp` keeps the path with the larger minimum capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use floyd_warshall_alg::FloydWarshall;
+    ///
+    /// let alg: FloydWarshall<f32> = FloydWarshall::widest_path();
+    /// ```
+    pub fn widest_path() -> Self {
+        let min = Box::new(|x: F, y: F| {
+            if x.partial_cmp(&y).unwrap_or(Greater) == Less {
+                x
+            } else {
+                y
+            }
+        });
+        let sharp_greater = Box::new(|x: F, y: F| x.partial_cmp(&y).unwrap_or(Less) == Greater);
+
+        Self::new_customized(min, sharp_greater)
+    }
+
+    /// Create a new instance of FloydWarshall structure pre-wired for the most reliable path
+    /// problem: the `op` multiplies edge probabilities, and the `cmp` keeps the path with the
+    /// higher resulting probability.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use floyd_warshall_alg::FloydWarshall;
+    ///
+    /// let alg: FloydWarshall<f32> = FloydWarshall::most_reliable();
+    /// ```
+    pub fn most_reliable() -> Self {
+        let mul = Box::new(|x: F, y: F| x * y);
+        let sharp_greater = Box::new(|x: F, y: F| x.partial_cmp(&y).unwrap_or(Less) == Greater);
+
+        Self::new_customized(mul, sharp_greater)
+    }
+
     /// Find all the shortest paths (or best rated paths based on algorithm customized settings).
     ///
     /// The result of type FloydWarshallResult holds both:
@@ -134,28 +229,104 @@ impl<F: FloydWarshallTrait> FloydWarshall<F> {
     where
         N: NodeTrait,
     {
-        let mut path: Graph<N, F> = graph.clone();
-        let mut next: Graph<N, N> = Graph::with_capacity(graph.node_count(), graph.edge_count());
+        let (path, next) = self.compute_paths(graph, self.discard_loops);
+
+        FloydWarshallResult::new(path, next)
+    }
+
+    /// Find all the shortest paths (or best rated paths based on algorithm customized settings),
+    /// detecting negative cycles instead of returning garbage distances.
+    ///
+    /// This mirrors [`find_paths`](Self::find_paths), but always computes the diagonal of the
+    /// `path` matrix (the self-distance of every node), regardless of the `discard_loops`
+    /// setting, since that diagonal is what a negative cycle shows up on. If, after the
+    /// computation, some node's self-distance is "better than identity" (see [`NegativeCycle`]),
+    /// the offending node labels are returned as an error instead of a result.
+    ///
+    /// Negative cycle detection is only meaningful for min-plus-like semirings; it is gated
+    /// behind the `Num` bound on `F` so it can compare against `F::zero()`.
+    pub fn try_find_paths<N>(
+        &self,
+        graph: &Graph<N, F>,
+    ) -> Result<FloydWarshallResult<N, F>, NegativeCycle<N>>
+    where
+        N: NodeTrait,
+    {
+        let (path, next) = self.compute_paths(graph, false);
+
+        let negative_cycle_nodes: Vec<N> = graph
+            .nodes()
+            .filter(|&node| {
+                path.edge_weight(node, node)
+                    .is_some_and(|&self_weight| (self.cmp)(self_weight, F::zero()))
+            })
+            .collect();
+
+        if !negative_cycle_nodes.is_empty() {
+            return Err(NegativeCycle(negative_cycle_nodes));
+        }
+
+        Ok(FloydWarshallResult::new(path, next))
+    }
 
-        // Initialize next steps of each edge existing in `graph` with its end node.
-        for (a, b, _) in graph.all_edges() {
-            next.add_edge(a, b, b);
+    /// Run the core Floyd-Warshall triple loop over `graph`, returning the computed `path` and
+    /// `next` graphs.
+    ///
+    /// `discard_loops` controls whether calculation is skipped for loops (e.g. edges starting
+    /// and ending in the same node), independently of `self.discard_loops`, so that callers
+    /// needing the diagonal (e.g. negative cycle detection) can force it to be computed.
+    ///
+    /// Internally the `n` node labels are mapped to dense `0..n` indices and the loop runs over
+    /// flat `Vec`s indexed as `i * n + j`, turning the per-cell hash-map lookup/insertion that
+    /// `graph` would otherwise pay on every one of the `O(V^3)` iterations into O(1) array
+    /// indexing. The flat arrays are translated back into `Graph`s before returning, so the
+    /// public API is unaffected.
+    fn compute_paths<N>(&self, graph: &Graph<N, F>, discard_loops: bool) -> (Graph<N, F>, Graph<N, N>)
+    where
+        N: NodeTrait,
+    {
+        let nodes: Vec<N> = graph.nodes().collect();
+        let node_count = nodes.len();
+
+        let index_of: HashMap<N, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, &node)| (node, index))
+            .collect();
+
+        let mut dist: Vec<Option<F>> = vec![None; node_count * node_count];
+        let mut next: Vec<Option<usize>> = vec![None; node_count * node_count];
+
+        // Seed `dist`/`next` with the input edges. If the graph is undirected, seed both
+        // `(a, b, w)` and its mirror `(b, a, w)`, so shortest paths are found in both directions
+        // without the caller duplicating edges.
+        for (a, b, &weight) in graph.all_edges() {
+            let i = index_of[&a];
+            let j = index_of[&b];
+
+            dist[i * node_count + j] = Some(weight);
+            next[i * node_count + j] = Some(j);
+
+            if self.undirected {
+                dist[j * node_count + i] = Some(weight);
+                next[j * node_count + i] = Some(i);
+            }
         }
 
         // `k` is the "intermediate" node which is currently considered.
-        for k in graph.nodes() {
+        for k in 0..node_count {
             // `i` is a starting node of the path we try to optimize.
-            for i in graph.nodes() {
+            for i in 0..node_count {
                 // `j` is an end node of the path we try to optimize.
-                for j in graph.nodes() {
+                for j in 0..node_count {
                     // Skip calculation for loops if requested.
-                    if self.discard_loops && !Self::unique(vec![k, i, j]) {
+                    if discard_loops && !Self::unique(vec![k, i, j]) {
                         continue;
                     }
 
                     // Calculation of a new weight of the path from `i` to `j` through `k`.
-                    let left_operand = path.edge_weight(i, k);
-                    let right_operand = path.edge_weight(k, j);
+                    let left_operand = dist[i * node_count + k];
+                    let right_operand = dist[k * node_count + j];
 
                     // There's nothing to calculate if the left `(i, k)` or right `(k, j)` path misses.
                     if left_operand.is_none() || right_operand.is_none() {
@@ -167,31 +338,46 @@ impl<F: FloydWarshallTrait> FloydWarshall<F> {
                     let right_operand = right_operand.unwrap();
 
                     // Use the weight operator. It can be customized.
-                    let new_weight = (self.op)(*left_operand, *right_operand);
+                    let new_weight = (self.op)(left_operand, right_operand);
 
                     // The `(i, j)` path already exists.
-                    if let Some(&old_weight) = path.edge_weight(i, j) {
+                    if let Some(old_weight) = dist[i * node_count + j] {
                         // Use the comparison. It can be customized.
                         if (self.cmp)(new_weight, old_weight) {
-                            path.add_edge(i, j, new_weight);
+                            dist[i * node_count + j] = Some(new_weight);
 
-                            // The algorithm invariants guarantee the edge exists.
-                            let direction = next.edge_weight(i, k).unwrap();
-                            next.add_edge(i, j, *direction);
+                            // The algorithm invariants guarantee the entry exists.
+                            next[i * node_count + j] = next[i * node_count + k];
                         }
                     } else {
                         // The path was missing so add a new one.
-                        path.add_edge(i, j, new_weight);
+                        dist[i * node_count + j] = Some(new_weight);
 
-                        // The algorithm invariants guarantee the edge exists.
-                        let direction = next.edge_weight(i, k).unwrap();
-                        next.add_edge(i, j, *direction);
+                        // The algorithm invariants guarantee the entry exists.
+                        next[i * node_count + j] = next[i * node_count + k];
                     }
                 }
             }
         }
 
-        FloydWarshallResult::new(path, next)
+        // Translate the flat arrays back into `Graph`s.
+        let mut path: Graph<N, F> = Graph::with_capacity(node_count, node_count * node_count);
+        let mut next_graph: Graph<N, N> =
+            Graph::with_capacity(node_count, node_count * node_count);
+
+        for i in 0..node_count {
+            for j in 0..node_count {
+                if let Some(weight) = dist[i * node_count + j] {
+                    path.add_edge(nodes[i], nodes[j], weight);
+                }
+
+                if let Some(next_index) = next[i * node_count + j] {
+                    next_graph.add_edge(nodes[i], nodes[j], nodes[next_index]);
+                }
+            }
+        }
+
+        (path, next_graph)
     }
 
     /// Are elements unique (no duplicates present).
@@ -236,9 +422,47 @@ mod tests {
         let mul = Box::new(|x: f32, y: f32| x * y);
         let sharp_less = Box::new(|x: f32, y: f32| x.partial_cmp(&y).unwrap_or(Greater) == Less);
         let discard_loops = false;
+        let undirected = false;
 
         let _alg: FloydWarshall<f32> =
-            FloydWarshall::new_fully_customized(mul, sharp_less, discard_loops);
+            FloydWarshall::new_fully_customized(mul, sharp_less, discard_loops, undirected);
+    }
+
+    #[test]
+    fn transitive_closure() {
+        let alg: FloydWarshall<u8> = FloydWarshall::transitive_closure();
+
+        // `a -> b -> c` makes `c` reachable from `a`, even though there is no direct edge.
+        let graph = Graph::<_, _>::from_edges(&[("a", "b", 1), ("b", "c", 1)]);
+
+        let result = alg.find_paths(&graph);
+
+        assert_eq!(result.path.edge_weight("a", "c"), Some(&1));
+    }
+
+    #[test]
+    fn widest_path() {
+        let alg: FloydWarshall<f32> = FloydWarshall::widest_path();
+
+        // The direct `(a, c)` path is narrower than going through `b`.
+        let graph = Graph::<_, _>::from_edges(&[("a", "b", 5.0), ("b", "c", 4.0), ("a", "c", 2.0)]);
+
+        let result = alg.find_paths(&graph);
+
+        assert_eq!(result.path.edge_weight("a", "c"), Some(&4.0));
+    }
+
+    #[test]
+    fn most_reliable() {
+        let alg: FloydWarshall<f32> = FloydWarshall::most_reliable();
+
+        // Going through `b` (`0.9 * 0.9 = 0.81`) is more reliable than the direct edge (`0.5`).
+        let graph = Graph::<_, _>::from_edges(&[("a", "b", 0.9), ("b", "c", 0.9), ("a", "c", 0.5)]);
+
+        let result = alg.find_paths(&graph);
+
+        let reliability = *result.path.edge_weight("a", "c").unwrap();
+        assert!((reliability - 0.81).abs() < 1e-5);
     }
 
     #[test]
@@ -293,4 +517,43 @@ mod tests {
         assert_eq!(next.edge_weight("g", "h"), Some(&"f"));
         assert_eq!(next.edge_weight("f", "h"), Some(&"h"));
     }
+
+    #[test]
+    fn try_find_paths_ok() {
+        let alg: FloydWarshall<f32> = FloydWarshall::new();
+
+        let graph = Graph::<_, _>::from_edges(&[("a", "b", 1.0), ("b", "c", 2.0)]);
+
+        let result = alg.try_find_paths(&graph).unwrap();
+
+        assert_eq!(result.path.edge_weight("a", "c"), Some(&3.0));
+    }
+
+    #[test]
+    fn try_find_paths_negative_cycle() {
+        let alg: FloydWarshall<f32> = FloydWarshall::new();
+
+        // `a -> b -> c -> a` sums to `-1.0`, a negative cycle through all three nodes.
+        let graph = Graph::<_, _>::from_edges(&[("a", "b", 1.0), ("b", "c", 1.0), ("c", "a", -3.0)]);
+
+        let error = alg.try_find_paths(&graph).unwrap_err();
+
+        assert!(error.0.contains(&"a"));
+        assert!(error.0.contains(&"b"));
+        assert!(error.0.contains(&"c"));
+    }
+
+    #[test]
+    fn find_paths_undirected() {
+        let mut alg: FloydWarshall<f32> = FloydWarshall::new();
+        alg.set_undirected(true);
+
+        let graph = Graph::<_, _>::from_edges(&[("a", "b", 1.0), ("b", "c", 2.0)]);
+
+        let result = alg.find_paths(&graph);
+
+        // The mirrored `(c, a)` path is found even though only `(a, b)` and `(b, c)` were given.
+        assert_eq!(result.path.edge_weight("c", "a"), Some(&3.0));
+        assert_eq!(result.path.edge_weight("a", "c"), Some(&3.0));
+    }
 }
@@ -1,5 +1,5 @@
 pub mod floyd_warshall;
 pub mod result;
 
-pub use crate::floyd_warshall::{FloydWarshall, FloydWarshallTrait};
+pub use crate::floyd_warshall::{FloydWarshall, FloydWarshallTrait, NegativeCycle};
 pub use crate::result::FloydWarshallResult;
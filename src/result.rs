@@ -0,0 +1,98 @@
+//! Floyd-Warshall algorithm result.
+
+use crate::floyd_warshall::FloydWarshallTrait;
+use safe_graph::{Graph, NodeTrait};
+
+/// Result of running the Floyd-Warshall algorithm.
+///
+/// Holds both:
+/// - best rated (shortest) weights for all possible paths
+/// - next node on the best rated (shortest) path for each possible path
+#[derive(Debug)]
+pub struct FloydWarshallResult<N: NodeTrait, F: FloydWarshallTrait> {
+    /// Best rated (shortest) path weights between every pair of nodes.
+    pub path: Graph<N, F>,
+    /// Next node on the best rated (shortest) path between every pair of nodes.
+    pub next: Graph<N, N>,
+}
+
+impl<N: NodeTrait, F: FloydWarshallTrait> FloydWarshallResult<N, F> {
+    /// Create a new instance of `FloydWarshallResult`.
+    pub fn new(path: Graph<N, F>, next: Graph<N, N>) -> Self {
+        Self { path, next }
+    }
+
+    /// Reconstruct the best rated (shortest) path from `from` to `to` as a sequence of nodes,
+    /// e.g. `1 -> 3 -> 4 -> 2`.
+    ///
+    /// Returns `None` when no path exists between `from` and `to`. Returns `Some(vec![from])`
+    /// when `from == to`. The walk is bounded to the number of nodes in `next` so that a
+    /// negative cycle that slipped through (e.g. via [`find_paths`](crate::FloydWarshall::find_paths)
+    /// instead of `try_find_paths`) cannot spin the reconstruction forever; `None` is returned
+    /// if the bound is exceeded.
+    pub fn reconstruct_path(&self, from: N, to: N) -> Option<Vec<N>> {
+        if from == to {
+            return Some(vec![from]);
+        }
+
+        self.next.edge_weight(from, to)?;
+
+        let max_steps = self.next.node_count();
+        let mut nodes = vec![from];
+        let mut current = from;
+
+        while current != to {
+            if nodes.len() > max_steps {
+                return None;
+            }
+
+            current = *self.next.edge_weight(current, to)?;
+            nodes.push(current);
+        }
+
+        Some(nodes)
+    }
+
+    /// Total cost of the best rated (shortest) path from `from` to `to`, if one exists.
+    pub fn total_cost(&self, from: N, to: N) -> Option<&F> {
+        self.path.edge_weight(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::floyd_warshall::FloydWarshall;
+    use safe_graph::Graph;
+
+    #[test]
+    fn reconstruct_path() {
+        let alg: FloydWarshall<f32> = FloydWarshall::new();
+
+        let graph = Graph::<_, _>::from_edges(&[
+            ("a", "g", 0.8),
+            ("g", "f", 0.6),
+            ("f", "h", 1.0),
+        ]);
+
+        let result = alg.find_paths(&graph);
+
+        assert_eq!(
+            result.reconstruct_path("a", "h"),
+            Some(vec!["a", "g", "f", "h"])
+        );
+        assert_eq!(result.reconstruct_path("a", "a"), Some(vec!["a"]));
+        assert_eq!(result.reconstruct_path("h", "a"), None);
+    }
+
+    #[test]
+    fn total_cost() {
+        let alg: FloydWarshall<f32> = FloydWarshall::new();
+
+        let graph = Graph::<_, _>::from_edges(&[("a", "b", 1.0), ("b", "c", 2.0)]);
+
+        let result = alg.find_paths(&graph);
+
+        assert_eq!(result.total_cost("a", "c"), Some(&3.0));
+        assert_eq!(result.total_cost("c", "a"), None);
+    }
+}